@@ -16,9 +16,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+mod migration;
 mod utils;
 
 pub use sp_database::Database;
+pub use migration::CURRENT_VERSION as CURRENT_DB_VERSION;
+pub use utils::{open_database_kind, DatabaseKind};
 
 use codec::{Decode, Encode};
 use parking_lot::Mutex;
@@ -50,6 +53,13 @@ pub enum DatabaseSettingsSrc {
 		/// Cache size in MiB.
 		cache_size: usize,
 	},
+	/// Load a ParityDb database from a given path. Faster point lookups and a smaller on-disk
+	/// footprint than RocksDb, which suits the mostly point-lookup access pattern of the
+	/// mapping/meta columns here.
+	ParityDb {
+		/// Path to the database.
+		path: PathBuf,
+	},
 }
 
 impl DatabaseSettingsSrc {
@@ -57,12 +67,13 @@ impl DatabaseSettingsSrc {
 	pub fn path(&self) -> Option<&Path> {
 		match self {
 			DatabaseSettingsSrc::RocksDb { path, .. } => Some(path.as_path()),
+			DatabaseSettingsSrc::ParityDb { path } => Some(path.as_path()),
 		}
 	}
 }
 
 pub(crate) mod columns {
-	pub const NUM_COLUMNS: u32 = 6;
+	pub const NUM_COLUMNS: u32 = 7;
 
 	pub const META: u32 = 0;
 	pub const BLOCK_MAPPING: u32 = 1;
@@ -70,6 +81,7 @@ pub(crate) mod columns {
 	pub const BLOCK_ID_MAPPING: u32 = 3; // store synced block id -> hash mapping
 	pub const BLOCK_HASH_MAPPING: u32 = 4; // store synced substrate block hash -> eth hash mapping
 	pub const ETH_BLOCK_TX_MAPPING: u32 = 5; // store synced eth block hash -> eth tx hash mapping
+	pub const SYNCED_NO_ETH: u32 = 6; // marks a synced substrate block hash that carried no Ethereum log
 }
 
 #[derive(Debug, Clone, Encode, Decode)]
@@ -81,6 +93,8 @@ pub struct SyncedBlockInfo<Block: BlockT> {
 pub(crate) mod static_keys {
 	pub const CURRENT_SYNCING_TIPS: &[u8] = b"CURRENT_SYNCING_TIPS";
 	pub const LAST_SYNCED_BLOCK: &[u8] = b"LAST_SYNCED_BLOCK";
+	/// Schema version of this database, see [`crate::migration`].
+	pub const DB_VERSION: &[u8] = b"DB_VERSION";
 }
 
 pub struct Backend<Block: BlockT> {
@@ -91,6 +105,7 @@ pub struct Backend<Block: BlockT> {
 impl<Block: BlockT> Backend<Block> {
 	pub fn new(config: &DatabaseSettings) -> Result<Self, String> {
 		let db = utils::open_database(config)?;
+		migration::migrate::<Block>(&db)?;
 
 		Ok(Self {
 			mapping: Arc::new(MappingDb {
@@ -176,6 +191,38 @@ impl<Block: BlockT> MetaDb<Block> {
 		Ok(())
 	}
 
+	/// Batched counterpart to [`Self::write_last_synced_block`]: records a `BLOCK_ID_MAPPING`
+	/// entry for every `(number, hash)` pair in `synced`, then stamps `LAST_SYNCED_BLOCK` once,
+	/// all in a single transaction. Used when a contiguous run of blocks is synced together so
+	/// `get_synced_block_hash` keeps working for every block in the run, not just the last one.
+	pub fn write_last_synced_block_batch(
+		&self,
+		synced: &[(NumberFor<Block>, Block::Hash)],
+		last: &SyncedBlockInfo<Block>,
+	) -> Result<(), String> {
+		log::debug!(target: "fc-db", "write last synced block (batch of {}): {:?}", synced.len(), last);
+		let mut transaction = sp_database::Transaction::new();
+
+		transaction.set(
+			crate::columns::META,
+			crate::static_keys::LAST_SYNCED_BLOCK,
+			&last.encode(),
+		);
+
+		for (number, hash) in synced {
+			transaction.set(
+				crate::columns::BLOCK_ID_MAPPING,
+				&number.encode(),
+				&hash.encode(),
+			);
+		}
+
+		self.db
+			.commit(transaction)
+			.map_err(|e| format!("{:?}", e))?;
+		Ok(())
+	}
+
 	pub fn get_synced_block_hash(&self, number: &NumberFor<Block>) -> Result<Block::Hash, String> {
 		match self
 			.db
@@ -297,11 +344,31 @@ impl<Block: BlockT> MappingDb<Block> {
 		}
 	}
 
+	/// Whether `block_hash` has been synced, whether or not it carried an Ethereum log: either it
+	/// has a `BLOCK_HASH_MAPPING` entry, or it was recorded via [`Self::write_none`] as a block
+	/// with no Ethereum log.
+	pub fn is_synced(&self, block_hash: &Block::Hash) -> Result<bool, String> {
+		Ok(self
+			.db
+			.get(crate::columns::BLOCK_HASH_MAPPING, &block_hash.encode())
+			.is_some()
+			|| self
+				.db
+				.get(crate::columns::SYNCED_NO_ETH, &block_hash.encode())
+				.is_some())
+	}
+
 	pub fn write_none(&self, block_hash: Block::Hash) -> Result<(), String> {
 		let _lock = self.write_lock.lock();
 
 		let mut transaction = sp_database::Transaction::new();
 
+		transaction.set(
+			crate::columns::SYNCED_NO_ETH,
+			&block_hash.encode(),
+			&().encode(),
+		);
+
 		self.db
 			.commit(transaction)
 			.map_err(|e| format!("{:?}", e))?;
@@ -359,9 +426,92 @@ impl<Block: BlockT> MappingDb<Block> {
 		Ok(())
 	}
 
+	/// Write a contiguous run of mapping commitments in a single database transaction.
+	///
+	/// Behaves like calling [`Self::write_hashes`] once per commitment, except all the
+	/// `BLOCK_MAPPING`/`BLOCK_HASH_MAPPING`/`ETH_BLOCK_TX_MAPPING`/`TRANSACTION_MAPPING` writes
+	/// are accumulated into one `sp_database::Transaction` and committed once, which avoids one
+	/// fsync-bearing commit per block during catch-up sync.
+	pub fn write_hashes_batch(&self, commitments: Vec<MappingCommitment<Block>>) -> Result<(), String> {
+		let _lock = self.write_lock.lock();
+
+		let mut transaction = sp_database::Transaction::new();
+		// Metadata staged by earlier commitments of this batch, so a transaction hash shared by
+		// several commitments keeps every entry, as sequential `write_hashes` calls would.
+		let mut pending_metadata =
+			std::collections::HashMap::<H256, Vec<TransactionMetadata<Block>>>::new();
+
+		for commitment in commitments {
+			transaction.set(
+				crate::columns::BLOCK_MAPPING,
+				&commitment.ethereum_block_hash.encode(),
+				&commitment.block_hash.encode(),
+			);
+
+			transaction.set(
+				crate::columns::BLOCK_HASH_MAPPING,
+				&commitment.block_hash.encode(),
+				&commitment.ethereum_block_hash.encode(),
+			);
+
+			if !commitment.ethereum_transaction_hashes.is_empty() {
+				transaction.set(
+					crate::columns::ETH_BLOCK_TX_MAPPING,
+					&commitment.ethereum_block_hash.encode(),
+					&commitment.ethereum_transaction_hashes.encode(),
+				);
+			}
+
+			for (i, ethereum_transaction_hash) in commitment
+				.ethereum_transaction_hashes
+				.into_iter()
+				.enumerate()
+			{
+				let metadata = match pending_metadata.entry(ethereum_transaction_hash) {
+					std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+					std::collections::hash_map::Entry::Vacant(entry) => {
+						entry.insert(self.transaction_metadata(&ethereum_transaction_hash)?)
+					}
+				};
+				metadata.push(TransactionMetadata::<Block> {
+					block_hash: commitment.block_hash,
+					ethereum_block_hash: commitment.ethereum_block_hash,
+					ethereum_index: i as u32,
+				});
+			}
+		}
+
+		for (ethereum_transaction_hash, metadata) in pending_metadata {
+			transaction.set(
+				crate::columns::TRANSACTION_MAPPING,
+				&ethereum_transaction_hash.encode(),
+				&metadata.encode(),
+			);
+		}
+
+		self.db
+			.commit(transaction)
+			.map_err(|e| format!("{:?}", e))?;
+
+		Ok(())
+	}
+
 	/// remove mapped data by the block hash
 	pub fn rollback_block_by_id(&self, hash: &Block::Hash) -> Result<(), String> {
-		let eth_block_hash = self.eth_block_hash_from_substrate_hash(hash)?;
+		let eth_block_hash = match self.eth_block_hash_from_substrate_hash(hash) {
+			Ok(eth_block_hash) => eth_block_hash,
+			Err(_) if self.db.get(crate::columns::SYNCED_NO_ETH, &hash.encode()).is_some() => {
+				// This block carried no Ethereum log, so there is nothing mapped to unwind:
+				// just clear its marker.
+				let mut transaction = sp_database::Transaction::new();
+				transaction.remove(crate::columns::SYNCED_NO_ETH, &hash.encode());
+				self.db
+					.commit(transaction)
+					.map_err(|e| format!("{:?}", e))?;
+				return Ok(());
+			}
+			Err(e) => return Err(e),
+		};
 
 		let txes = self.eth_transactions(&eth_block_hash)?;
 
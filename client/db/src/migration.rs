@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2021 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use codec::{Decode, Encode};
+use sp_database::{Database, Transaction};
+use sp_runtime::traits::Block as BlockT;
+
+use crate::{columns, static_keys, DbHash, SyncedBlockInfo};
+
+/// Current on-disk schema version. Bump this and append a migration to [`migrations`] whenever a
+/// change to key layout or encoding (e.g. a new backend, a new column, a changed metadata
+/// encoding) would otherwise silently mis-read an older database.
+pub const CURRENT_VERSION: u32 = 2;
+
+/// A single migration step: given the database, stage whatever writes are needed into
+/// `transaction`. The runner commits the staged writes together with the bumped version number,
+/// so a step never needs to (and must not) commit on its own.
+type Migration = fn(&dyn Database<DbHash>, &mut Transaction<DbHash>) -> Result<(), String>;
+
+/// Ordered migrations, indexed by the version they migrate *to*: `migrations()[0]` takes a
+/// database from version 0 to version 1, `migrations()[1]` from 1 to 2, and so on.
+fn migrations<Block: BlockT>() -> Vec<Migration> {
+	vec![migrate_to_v1::<Block>, migrate_to_v2]
+}
+
+/// Pre-versioning databases tracked sync progress with a single `LAST_SYNCED_BLOCK` pointer and
+/// no `CURRENT_SYNCING_TIPS` entry at all (the multi-tip frontier didn't exist yet). Seed the tip
+/// set from that pointer so an upgraded database resumes as a single-tip walk-back from where the
+/// old linear pointer left off.
+fn migrate_to_v1<Block: BlockT>(
+	db: &dyn Database<DbHash>,
+	transaction: &mut Transaction<DbHash>,
+) -> Result<(), String> {
+	if db.get(columns::META, static_keys::CURRENT_SYNCING_TIPS).is_some() {
+		return Ok(());
+	}
+
+	if let Some(raw) = db.get(columns::META, static_keys::LAST_SYNCED_BLOCK) {
+		let last_synced = SyncedBlockInfo::<Block>::decode(&mut &raw[..])
+			.map_err(|e| format!("{:?}", e))?;
+		let tips = vec![last_synced.hash];
+
+		transaction.set(
+			columns::META,
+			static_keys::CURRENT_SYNCING_TIPS,
+			&tips.encode(),
+		);
+	}
+
+	Ok(())
+}
+
+/// Introduces the `SYNCED_NO_ETH` column used to mark synced blocks that carried no Ethereum log.
+/// The column starts empty for every existing database, so there is no prior data to rewrite;
+/// `write_none` simply starts populating it for newly-synced blocks from here on.
+fn migrate_to_v2(_db: &dyn Database<DbHash>, _transaction: &mut Transaction<DbHash>) -> Result<(), String> {
+	Ok(())
+}
+
+fn read_version(db: &dyn Database<DbHash>) -> Result<u32, String> {
+	match db.get(columns::META, static_keys::DB_VERSION) {
+		Some(raw) => u32::decode(&mut &raw[..]).map_err(|e| format!("{:?}", e)),
+		None => Ok(0),
+	}
+}
+
+/// Run any outstanding migrations to bring `db` up to [`CURRENT_VERSION`].
+///
+/// Each migration commits its writes and the bumped version number in a single
+/// `sp_database::Transaction`, so a crash mid-migration leaves the database at its previous,
+/// fully-consistent version rather than half-migrated; calling `migrate` again simply resumes
+/// from there.
+pub fn migrate<Block: BlockT>(db: &Arc<dyn Database<DbHash>>) -> Result<(), String> {
+	let migrations = migrations::<Block>();
+	let mut version = read_version(db.as_ref())?;
+
+	while (version as usize) < migrations.len() {
+		let step = migrations[version as usize];
+		let mut transaction = Transaction::new();
+
+		log::info!(
+			target: "fc-db",
+			"migrating mapping database from version {} to {}",
+			version,
+			version + 1,
+		);
+		step(db.as_ref(), &mut transaction)?;
+
+		version += 1;
+		transaction.set(columns::META, static_keys::DB_VERSION, &version.encode());
+
+		db.commit(transaction).map_err(|e| format!("{:?}", e))?;
+	}
+
+	Ok(())
+}
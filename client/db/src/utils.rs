@@ -0,0 +1,133 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2021 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+use sp_database::Database;
+
+use crate::{columns, DatabaseSettings, DatabaseSettingsSrc, DbHash};
+
+/// The storage engine an on-disk database was created with. Used to make sure a node never
+/// silently opens a database with a different engine than the one it was written with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseKind {
+	/// RocksDB, identified by the `CURRENT` file RocksDB maintains at the root of its database
+	/// directory.
+	RocksDb,
+	/// ParityDb, identified by the `metadata` file ParityDb writes at the root of its database
+	/// directory.
+	ParityDb,
+}
+
+/// Detect the storage engine of an existing on-disk database at `path`.
+///
+/// Returns `None` if `path` does not exist yet or does not contain a database recognizable as
+/// either engine, in which case it is safe to create a fresh database there.
+pub fn open_database_kind(path: &Path) -> Option<DatabaseKind> {
+	if path.join("CURRENT").is_file() {
+		Some(DatabaseKind::RocksDb)
+	} else if path.join("metadata").is_file() {
+		Some(DatabaseKind::ParityDb)
+	} else {
+		None
+	}
+}
+
+fn check_database_kind(path: &Path, expected: DatabaseKind) -> Result<(), String> {
+	match open_database_kind(path) {
+		Some(found) if found != expected => Err(format!(
+			"Trying to open a {:?} database at {:?}, but it was created as {:?}",
+			expected, path, found,
+		)),
+		_ => Ok(()),
+	}
+}
+
+pub fn open_database(config: &DatabaseSettings) -> Result<Arc<dyn Database<DbHash>>, String> {
+	let db: Arc<dyn Database<DbHash>> = match &config.source {
+		DatabaseSettingsSrc::RocksDb { path, cache_size } => {
+			check_database_kind(path, DatabaseKind::RocksDb)?;
+
+			let mut db_config = kvdb_rocksdb::DatabaseConfig::with_columns(columns::NUM_COLUMNS);
+			let path = path
+				.to_str()
+				.ok_or_else(|| "Invalid database path".to_string())?;
+			// `cache_size` is expressed in MiB; spread it evenly across all columns.
+			let column_budget = cache_size.saturating_mul(1024 * 1024) / columns::NUM_COLUMNS as usize;
+			db_config.memory_budget = (0..columns::NUM_COLUMNS)
+				.map(|col| (col, column_budget))
+				.collect::<HashMap<_, _>>();
+
+			let db = kvdb_rocksdb::Database::open(&db_config, path)
+				.map_err(|err| format!("{}", err))?;
+			sp_database::as_database(db)
+		}
+		DatabaseSettingsSrc::ParityDb { path } => {
+			check_database_kind(path, DatabaseKind::ParityDb)?;
+
+			open_parity_db(path)?
+		}
+	};
+
+	Ok(db)
+}
+
+fn open_parity_db(path: &Path) -> Result<Arc<dyn Database<DbHash>>, String> {
+	// Default `ColumnOptions` (no `preimage`/`uniform`) for every column: those flags assume
+	// fixed-size, content-addressed keys (key == hash(value)), which holds for none of our
+	// columns (`META`'s keys are variable-length ASCII, `BLOCK_ID_MAPPING`'s are 4-byte block
+	// numbers, and the rest are plain lookup keys, not hashes of their values).
+	let options = parity_db::Options::with_columns(path, columns::NUM_COLUMNS as u8);
+
+	let db = parity_db::Db::open_or_create(&options).map_err(|err| format!("{}", err))?;
+
+	Ok(Arc::new(parity_db_adapter::ParityDbAdapter(db)))
+}
+
+/// Thin adapter implementing `sp_database::Database` on top of a `parity_db::Db`, mirroring the
+/// `kvdb`-backed RocksDB path so the rest of `fc-db` stays engine-agnostic.
+mod parity_db_adapter {
+	use super::*;
+	use sp_database::{error, Change, ColumnId, Transaction};
+
+	pub struct ParityDbAdapter(pub parity_db::Db);
+
+	impl<H: Clone + Send + Sync + Eq + PartialEq + Default + AsRef<[u8]>> Database<H>
+		for ParityDbAdapter
+	{
+		fn commit(&self, transaction: Transaction<H>) -> error::Result<()> {
+			self.0
+				.commit(transaction.0.into_iter().filter_map(|change| match change {
+					Change::Set(col, key, value) => Some((col as u8, key, Some(value))),
+					Change::Remove(col, key) => Some((col as u8, key, None)),
+					// Ref-counted preimage columns aren't used by any column here (see
+					// `open_parity_db`), so the ref-counting variants never occur in practice;
+					// drop them rather than panicking if they ever do.
+					_ => None,
+				}))
+				.map_err(|e| error::DatabaseError(Box::new(std::io::Error::new(
+					std::io::ErrorKind::Other,
+					format!("{}", e),
+				))))
+		}
+
+		fn get(&self, col: ColumnId, key: &[u8]) -> Option<Vec<u8>> {
+			self.0.get(col as u8, key).ok().flatten()
+		}
+	}
+}
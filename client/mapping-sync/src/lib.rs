@@ -84,57 +84,54 @@ pub fn sync_genesis_block<Block: BlockT, C>(
 	Ok(())
 }
 
-pub fn rollback_last_block<Block: BlockT>(
-	frontier_backend: &fc_db::Backend<Block>,
-) -> Result<bool, String>
-{
-	let last_synced_block = frontier_backend.meta().last_synced_block()
-		.map_err(|e| format!("{:?}", e))?
-		.ok_or("failed to get last synced block")?;
-	log::debug!(target: "mapping-sync", "rollback block: {:?}", last_synced_block);
-	frontier_backend.mapping().rollback_block_by_id(&last_synced_block.hash)?;
-	frontier_backend.meta().remove_block(&last_synced_block)?;
+// Reorg contract: a linear `LAST_SYNCED_BLOCK` pointer plus an active rollback loop (walking
+// that pointer back block-by-block until it lands on a hash the Substrate chain still
+// recognises) doesn't fit the multi-tip model below, where several forks can be mid-walk-back
+// at once and there is no single "last synced" position to unwind from. Instead, each fork gets
+// its own entry in `CURRENT_SYNCING_TIPS`, walked back independently until it reaches a block
+// `is_synced` already considers mapped; blocks mapped by a fork that later loses a race are left
+// in place rather than rolled back, since `BLOCK_HASH_MAPPING` is keyed by substrate block
+// hash, not canonical-chain position, so a stale entry for an abandoned fork is simply never
+// looked up again (`BLOCK_ID_MAPPING`, keyed by block number, keeps whichever hash was written
+// last for that number). `fc_db::MappingDb::rollback_block_by_id` remains available as a primitive for
+// callers that do need to unwind a specific block by hash; mapping-sync itself doesn't call it.
 
-	if last_synced_block.number <= 0u32.into() { // already at genesis block, clear the last synced block
-		frontier_backend.meta().clear_last_synced_block()?;
-	} else {
-		// should set the last synced block to the parent
-		let number = last_synced_block.number - 1u32.into();
-		let hash = frontier_backend.meta().get_synced_block_hash(&number)?;
-		frontier_backend.meta().write_last_synced_block(&hash, &number)?;
-	}
-
-	Ok(true)
+/// Whether `hash` has already been mapped, i.e. `sync_block` has recorded it (with or without an
+/// Ethereum log) and there is nothing left to do for it or its ancestors.
+fn is_synced<Block: BlockT>(
+	frontier_backend: &fc_db::Backend<Block>,
+	hash: &Block::Hash,
+) -> Result<bool, String> {
+	frontier_backend.mapping().is_synced(hash)
 }
 
-pub fn eusure_synced_blocks<Block: BlockT, B>(
+/// Make sure the chain's current best block is tracked as a syncing tip. A best block whose
+/// parent is already a tip is not added yet: a later call adds it once that parent tip has been
+/// popped and synced.
+fn track_new_best_tip<Block: BlockT, B>(
 	substrate_backend: &B,
 	frontier_backend: &fc_db::Backend<Block>,
-) -> Result<(), String> where
+	tips: &mut Vec<Block::Hash>,
+) -> Result<(), String>
+where
 	B: sp_blockchain::HeaderBackend<Block> + sp_blockchain::Backend<Block>,
 {
-	loop {
-		let last_synced_block = frontier_backend.meta().last_synced_block()?;
-		// have synced some blocks
-		if let Some(last_synced_block) = last_synced_block {
-			// need to check last synced block is still in the chain
-			// we need rollback to the last block that in the chain
-			let header_on_chain = substrate_backend.header(BlockId::Number(last_synced_block.number))
-				.map_err(|e| format!("{:?}", e))?;
-			if let Some(header_on_chain) = header_on_chain {
-				if header_on_chain.hash() != last_synced_block.hash {
-					log::debug!(target: "mapping-sync", "last synced block hash doesn't match with chain data, last: {:?}, on chain: {:?}", last_synced_block, header_on_chain);
-					rollback_last_block(frontier_backend)?;
-				} else {
-					break;
-				}
-			} else {
-				break;
-			}
-		} else {
-			break;
-		}
+	let best_hash = substrate_backend.info().best_hash;
+
+	if tips.contains(&best_hash) || is_synced(frontier_backend, &best_hash)? {
+		return Ok(());
+	}
+
+	let header = substrate_backend
+		.header(BlockId::Hash(best_hash))
+		.map_err(|e| format!("{:?}", e))?
+		.ok_or("Block header not found".to_string())?;
+
+	if !tips.contains(header.parent_hash()) {
+		log::debug!(target: "mapping-sync", "new syncing tip: {:?}", best_hash);
+		tips.push(best_hash);
 	}
+
 	Ok(())
 }
 
@@ -147,38 +144,66 @@ pub fn sync_one_block<Block: BlockT, C, B>(
 	C::Api: EthereumRuntimeRPCApi<Block>,
 	B: sp_blockchain::HeaderBackend<Block> + sp_blockchain::Backend<Block>,
 {
-	// make sure the synced blocks are on the main chain
-	eusure_synced_blocks(substrate_backend, frontier_backend)?;
-
-	let last_synced_block = frontier_backend.meta().last_synced_block()?;
-	// have synced some blocks
-	if let Some(last_synced_block) = last_synced_block {
-		let block_number = last_synced_block.number + 1u32.into();
-		if substrate_backend.info().best_number < block_number {
-			log::debug!(target: "mapping-sync", "{:?} is ahead of best block", block_number);
-			return Ok(false)
+	let mut tips = frontier_backend.meta().current_syncing_tips()?;
+
+	if tips.is_empty() {
+		if frontier_backend.meta().last_synced_block()?.is_some() {
+			// Nothing left to walk back to until a new best block gives us a fresh tip.
+			track_new_best_tip(substrate_backend, frontier_backend, &mut tips)?;
+			frontier_backend.meta().write_current_syncing_tips(tips.clone())?;
+			return Ok(!tips.is_empty())
 		}
 
-		let header = substrate_backend.header(BlockId::Number(last_synced_block.number + 1u32.into()))
+		log::info!(target: "mapping-sync", "start sync genesis block");
+		let genesis_header = substrate_backend.header(BlockId::Number(Zero::zero()))
 			.map_err(|e| format!("{:?}", e))?
-			.ok_or("Block header not found".to_string())?;
+			.ok_or("Genesis header not found".to_string())?;
+
+		sync_genesis_block(client, frontier_backend, &genesis_header)?;
+		frontier_backend.meta().write_last_synced_block(&genesis_header.hash(), &genesis_header.number())?;
 
-		sync_block(frontier_backend, &header)?;
-		frontier_backend.meta().write_last_synced_block(&header.hash(), &header.number())?;
+		tips.push(substrate_backend.info().best_hash);
+		frontier_backend.meta().write_current_syncing_tips(tips)?;
 
 		return Ok(true)
-	} else {
-		let header = substrate_backend.header(BlockId::Number(Zero::zero()))
-			.map_err(|e| format!("{:?}", e))?
-			.ok_or("Genesis header not found".to_string())?;
-        log::info!(target: "mapping-sync", "start sync genesis block");
-		// no block synced, start with genesis block
-		sync_genesis_block(client, frontier_backend, &header)?;
-		frontier_backend.meta().write_last_synced_block(&header.hash(), &header.number())?;
+	}
+
+	track_new_best_tip(substrate_backend, frontier_backend, &mut tips)?;
+
+	let tip_hash = tips.pop().expect("tips is non-empty, checked above; qed");
+
+	if is_synced(frontier_backend, &tip_hash)? {
+		// Some other tip's walk-back already reached this block.
+		frontier_backend.meta().write_current_syncing_tips(tips)?;
 		return Ok(true)
 	}
+
+	let header = substrate_backend.header(BlockId::Hash(tip_hash))
+		.map_err(|e| format!("{:?}", e))?
+		.ok_or("Block header not found".to_string())?;
+
+	sync_block(frontier_backend, &header)?;
+	frontier_backend.meta().write_last_synced_block(&header.hash(), &header.number())?;
+
+	let parent_hash = *header.parent_hash();
+	if header.number() > &Zero::zero() && !is_synced(frontier_backend, &parent_hash)? {
+		// Parent isn't mapped yet: keep walking this branch back.
+		tips.push(parent_hash);
+	}
+
+	frontier_backend.meta().write_current_syncing_tips(tips)?;
+
+	Ok(true)
 }
 
+/// Drive mapping sync forward by up to `limit` blocks.
+///
+/// Pops the most recently added syncing tip, walks back from it through consecutive,
+/// log-bearing blocks and commits that run via [`fc_db::MappingDb::write_hashes_batch`] in one
+/// database transaction instead of one commit per block, which matters during initial catch-up
+/// sync over millions of blocks. The first block on the way with no Ethereum log is marked via
+/// `write_none` and ends the run. Any other tips are left for later calls. When there is no tip
+/// yet, or the tip itself carries no Ethereum log, this falls back to [`sync_one_block`].
 pub fn sync_blocks<Block: BlockT, C, B>(
 	client: &C,
 	substrate_backend: &B,
@@ -189,11 +214,110 @@ pub fn sync_blocks<Block: BlockT, C, B>(
 	C::Api: EthereumRuntimeRPCApi<Block>,
 	B: sp_blockchain::HeaderBackend<Block> + sp_blockchain::Backend<Block>,
 {
-	let mut synced_any = false;
+	let mut tips = frontier_backend.meta().current_syncing_tips()?;
+	if tips.is_empty() {
+		return sync_one_block(client, substrate_backend, frontier_backend);
+	}
+	track_new_best_tip(substrate_backend, frontier_backend, &mut tips)?;
+
+	let tip_hash = tips.pop().expect("tips is non-empty, checked above; qed");
 
-	for _ in 0..limit {
-		synced_any = synced_any || sync_one_block(client, substrate_backend, frontier_backend)?;
+	if is_synced(frontier_backend, &tip_hash)? {
+		frontier_backend.meta().write_current_syncing_tips(tips)?;
+		return Ok(true)
 	}
 
-	Ok(synced_any)
+	let mut commitments = Vec::new();
+	let mut synced_headers = Vec::new();
+	// The tip's own header: this run's walk-back may reach further back in history, but
+	// `LAST_SYNCED_BLOCK` always tracks the newest block on this branch, matching what
+	// `sync_one_block` would leave behind if it processed the tip alone.
+	let mut newest_header = None;
+	let mut last_header = None;
+	let mut hash = tip_hash;
+
+	for _ in 0..limit.max(1) {
+		let header = substrate_backend.header(BlockId::Hash(hash))
+			.map_err(|e| format!("{:?}", e))?
+			.ok_or("Block header not found".to_string())?;
+
+		if newest_header.is_none() {
+			newest_header = Some(header.clone());
+		}
+
+		match fp_consensus::find_log(header.digest()) {
+			Ok(log) => {
+				let post_hashes = log.into_hashes();
+				commitments.push(fc_db::MappingCommitment {
+					block_hash: header.hash(),
+					ethereum_block_hash: post_hashes.block_hash,
+					ethereum_transaction_hashes: post_hashes.transaction_hashes,
+				});
+				synced_headers.push((header.number().clone(), header.hash()));
+			}
+			Err(FindLogError::NotFound) => {
+				if commitments.is_empty() {
+					// Nothing batched yet: let `sync_one_block` record the "no log" marker for
+					// this block on its own.
+					return sync_one_block(client, substrate_backend, frontier_backend);
+				}
+
+				// This block itself carries no log, but it still needs to be marked synced
+				// (via `write_none`/`SYNCED_NO_ETH`) right here: otherwise it's recorded
+				// nowhere, the next tip becomes its parent, and `is_synced` on it never
+				// turns true, permanently skipping it.
+				frontier_backend.mapping().write_hashes_batch(commitments)?;
+				frontier_backend.mapping().write_none(header.hash())?;
+				synced_headers.push((header.number().clone(), header.hash()));
+
+				let newest_header = newest_header
+					.expect("set for the tip on the first iteration; qed");
+				let info = fc_db::SyncedBlockInfo::<Block> {
+					hash: newest_header.hash(),
+					number: newest_header.number().clone(),
+				};
+				frontier_backend.meta().write_last_synced_block_batch(&synced_headers, &info)?;
+
+				let parent_hash = *header.parent_hash();
+				if header.number() > &Zero::zero() && !is_synced(frontier_backend, &parent_hash)? {
+					tips.push(parent_hash);
+				}
+				frontier_backend.meta().write_current_syncing_tips(tips)?;
+
+				return Ok(true)
+			}
+			Err(FindLogError::MultipleLogs) => return Err("Multiple logs found".to_string()),
+		}
+
+		let number = *header.number();
+		let parent_hash = *header.parent_hash();
+		last_header = Some(header);
+
+		if number <= Zero::zero() || is_synced(frontier_backend, &parent_hash)? {
+			break;
+		}
+		hash = parent_hash;
+	}
+
+	let last_header = last_header.expect("loop always sets it before breaking or exhausting; qed");
+	let newest_header = newest_header.expect("set for the tip on the first iteration; qed");
+
+	if !commitments.is_empty() {
+		frontier_backend.mapping().write_hashes_batch(commitments)?;
+	}
+
+	let info = fc_db::SyncedBlockInfo::<Block> {
+		hash: newest_header.hash(),
+		number: newest_header.number().clone(),
+	};
+	frontier_backend.meta().write_last_synced_block_batch(&synced_headers, &info)?;
+
+	let parent_hash = *last_header.parent_hash();
+	if last_header.number() > &Zero::zero() && !is_synced(frontier_backend, &parent_hash)? {
+		tips.push(parent_hash);
+	}
+
+	frontier_backend.meta().write_current_syncing_tips(tips)?;
+
+	Ok(true)
 }
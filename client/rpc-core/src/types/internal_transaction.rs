@@ -1,16 +1,100 @@
 use serde::{Serialize, };
 use ethereum_types::{H160, U256};
 
+use crate::types::Bytes;
+
+/// Kind of EVM call a trace frame represents, matching the `type` field of the standard
+/// `callTracer`/`debug_traceTransaction` output (`CALL`, `STATICCALL`, ...).
+#[derive(Debug, Clone, Copy, Serialize)]
+pub enum CallType {
+	#[serde(rename = "CALL")]
+	Call,
+	#[serde(rename = "STATICCALL")]
+	StaticCall,
+	#[serde(rename = "DELEGATECALL")]
+	DelegateCall,
+	#[serde(rename = "CALLCODE")]
+	CallCode,
+	#[serde(rename = "CREATE")]
+	Create,
+	#[serde(rename = "CREATE2")]
+	Create2,
+	#[serde(rename = "SELFDESTRUCT")]
+	SelfDestruct,
+}
+
 /// Internal Transaction for rpc
-#[derive(Debug, Serialize)]
+///
+/// One frame of an EVM call tree. `calls` holds nested sub-calls in execution order, so the whole
+/// tree can be serialized directly as the "callTracer" response to `trace_transaction` /
+/// `debug_traceTransaction`, or flattened via [`InternalTransaction::flatten`] into the "flat"
+/// one-row-per-call form that explorers expect.
+#[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct InternalTransaction {
+    #[serde(rename = "type")]
+    pub call_type: CallType,
     /// Sender
     pub from: Option<H160>,
     /// Recipient
     pub to: Option<H160>,
+    pub value: U256,
+    pub input: Bytes,
+    pub output: Bytes,
+    pub gas: U256,
     /// Gas used
-    pub gas_used: Option<U256>,
+    pub gas_used: U256,
+    pub error: Option<String>,
+    pub calls: Vec<InternalTransaction>,
+}
+
+/// A single row of the "flat" trace form: one call per entry, located within its parent
+/// transaction's call tree by `trace_address`, the path of child indices from the root call.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatInternalTransaction {
+    #[serde(rename = "type")]
+    pub call_type: CallType,
+    pub from: Option<H160>,
+    pub to: Option<H160>,
+    pub value: U256,
+    pub input: Bytes,
+    pub output: Bytes,
+    pub gas: U256,
+    pub gas_used: U256,
+    pub error: Option<String>,
+    pub trace_address: Vec<u32>,
+}
+
+impl InternalTransaction {
+    /// Flatten this call tree (depth-first, in call order) into the "flat" trace form, one row
+    /// per call, each carrying the `trace_address` path that locates it within the tree.
+    pub fn flatten(&self) -> Vec<FlatInternalTransaction> {
+        let mut flat = Vec::new();
+        self.flatten_into(&mut Vec::new(), &mut flat);
+        flat
+    }
+
+    fn flatten_into(&self, trace_address: &mut Vec<u32>, out: &mut Vec<FlatInternalTransaction>) {
+        out.push(FlatInternalTransaction {
+            call_type: self.call_type,
+            from: self.from,
+            to: self.to,
+            value: self.value,
+            input: self.input.clone(),
+            output: self.output.clone(),
+            gas: self.gas,
+            gas_used: self.gas_used,
+            error: self.error.clone(),
+            trace_address: trace_address.clone(),
+        });
+
+        for (index, call) in self.calls.iter().enumerate() {
+            trace_address.push(index as u32);
+            call.flatten_into(trace_address, out);
+            trace_address.pop();
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -26,4 +110,3 @@ pub struct InternalTxDetails {
 	pub tx: InternalTransaction,
 	pub reward: Option<RewardInfo>,
 }
-
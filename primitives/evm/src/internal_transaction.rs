@@ -1,11 +1,39 @@
-#![cfg_attr(not(feature = "std"), no_std)]
-
 use codec::{Encode, Decode};
 #[cfg(feature = "std")]
 use serde::{Serialize, Deserialize};
 
+use sp_std::prelude::*;
 use sp_core::{U256, H160};
-use crate::InternalTransaction;
+
+/// Kind of EVM call an [`InternalTransaction`] frame represents.
+#[derive(Clone, Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub enum CallType {
+	Call,
+	StaticCall,
+	DelegateCall,
+	CallCode,
+	Create,
+	Create2,
+	SelfDestruct,
+}
+
+/// A single frame of an EVM call tree, recording enough detail to reconstruct a full execution
+/// trace: the call kind, its inputs/outputs, and any nested sub-calls it made.
+#[derive(Clone, Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct InternalTransaction {
+	pub call_type: CallType,
+	pub from: Option<H160>,
+	pub to: Option<H160>,
+	pub value: U256,
+	pub input: Vec<u8>,
+	pub output: Vec<u8>,
+	pub gas: U256,
+	pub gas_used: U256,
+	pub error: Option<Vec<u8>>,
+	pub calls: Vec<InternalTransaction>,
+}
 
 #[derive(Clone, Eq, PartialEq, Encode, Decode)]
 #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]